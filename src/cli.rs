@@ -0,0 +1,26 @@
+//! CLI configuration: which transport to serve the calculator over.
+//!
+//! Defaults to stdio (the original behaviour, for locally-spawned MCP
+//! clients); `--transport sse` serves the same `Calculator` over rmcp's
+//! HTTP/SSE transport for remote/browser clients.
+
+use clap::{Parser, ValueEnum};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lower")]
+pub enum Transport {
+    Stdio,
+    Sse,
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "mcp-sdlt", about = "UK SDLT calculator MCP server")]
+pub struct Cli {
+    /// Which transport to serve the calculator over.
+    #[arg(long, value_enum, default_value_t = Transport::Stdio)]
+    pub transport: Transport,
+
+    /// Address to bind when `--transport sse` is selected.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub bind: String,
+}
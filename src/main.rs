@@ -1,24 +1,96 @@
+mod cli;
+mod metrics;
+mod modifiers;
+mod rates;
+mod resources;
+
+use chrono::NaiveDate;
+use clap::Parser;
+use cli::{Cli, Transport};
+use metrics::Metrics;
+use modifiers::BuyerCategory;
+use rates::{Jurisdiction, RateTables};
+use resources::{RateTablesChangedRx, RateTablesChangedTx};
 use rmcp::{
-    Error, ServerHandler, ServiceExt,
     model::{
-        CallToolResult, Content, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo,
+        CallToolResult, Content, Implementation, ListResourcesResult, PaginatedRequestParam,
+        ProtocolVersion, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+        ServerCapabilities, ServerInfo,
     },
     schemars, tool,
     transport::stdio,
+    Error, ServerHandler, ServiceExt,
 };
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Path to the rate-table config, overridable via `SDLT_RATES_CONFIG`.
+const DEFAULT_RATES_CONFIG_PATH: &str = "rates.toml";
 
 #[derive(Clone)]
-pub struct Calculator;
+pub struct Calculator {
+    rates_path: String,
+    rate_tables: Arc<RwLock<Arc<RateTables>>>,
+    rate_tables_changed: RateTablesChangedTx,
+    metrics: Arc<Metrics>,
+}
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub struct IpValidateRequest {
-    pub property_value: f64,
+    #[schemars(with = "String")]
+    pub property_value: Decimal,
+    /// Date of the transaction (drives which rate table applies).
+    #[schemars(with = "String")]
+    pub transaction_date: NaiveDate,
+    #[serde(default)]
+    pub jurisdiction: Jurisdiction,
+    #[serde(default)]
+    pub first_time_buyer: bool,
+    #[serde(default)]
+    pub additional_property: bool,
+    #[serde(default)]
+    pub non_resident: bool,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub struct AffordabilityRequest {
+    /// Total cash the buyer can spend: property price + SDLT combined.
+    #[schemars(with = "String")]
+    pub total_budget: Decimal,
+    #[schemars(with = "String")]
+    pub transaction_date: NaiveDate,
+    #[serde(default)]
+    pub jurisdiction: Jurisdiction,
 }
 
 #[tool(tool_box)]
 impl Calculator {
     fn new() -> Self {
-        Self
+        let rates_path = std::env::var("SDLT_RATES_CONFIG")
+            .unwrap_or_else(|_| DEFAULT_RATES_CONFIG_PATH.to_string());
+        let (rate_tables_changed, _rx) = resources::channel();
+        Self {
+            rate_tables: Arc::new(RwLock::new(Arc::new(RateTables::load(&rates_path)))),
+            rates_path,
+            rate_tables_changed,
+            metrics: Arc::new(Metrics::new()),
+        }
+    }
+
+    /// Subscribe to rate-table reload notifications (used to drive MCP
+    /// `resources/updated` notifications - see `resources`).
+    pub fn subscribe_rate_tables(&self) -> RateTablesChangedRx {
+        self.rate_tables_changed.subscribe()
+    }
+
+    /// Re-reads the rate-table config from disk and notifies subscribers.
+    /// Called on a budget-day update, via the `reload_rates` tool below.
+    async fn do_reload_rates(&self) {
+        let reloaded = Arc::new(RateTables::load(&self.rates_path));
+        *self.rate_tables.write().await = reloaded;
+        let _ = self.rate_tables_changed.send(resources::RateTablesChanged);
     }
 
     // phase 1, just to get the idea...
@@ -37,62 +109,200 @@ impl Calculator {
     /// £250k to £925k	5%
     /// £925k to £1.5m	10%
     /// rest over £1.5m	12%
+    ///
+    /// (these are the current England/NI defaults - the actual bands
+    /// applied depend on `transaction_date` and `jurisdiction`, see
+    /// `rates::RateTables`)
 
+    #[tracing::instrument(skip(self), fields(property_value = %property_value, ?jurisdiction))]
     #[tool(description = "Calculate UK SDLT - property tax")]
     async fn calculate_sdlt(
         &self,
-        #[tool(aggr)] IpValidateRequest { property_value }: IpValidateRequest,
+        #[tool(aggr)] IpValidateRequest {
+            property_value,
+            transaction_date,
+            jurisdiction,
+            first_time_buyer,
+            additional_property,
+            non_resident,
+        }: IpValidateRequest,
     ) -> Result<CallToolResult, Error> {
+        self.metrics.record_call();
+
         let property_value = match Some(property_value) {
             Some(val) => val,
             None => {
+                self.metrics.record_error();
                 return Ok(CallToolResult::success(vec![Content::text(
                     "Property value is missing.".to_string(),
                 )]));
             }
         };
 
-        let mut tax = 0.0;
+        let rate_tables = self.rate_tables.read().await.clone();
+        let Some(table) = rate_tables.select(jurisdiction, transaction_date) else {
+            self.metrics.record_error();
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No rate table found for {jurisdiction:?} on {transaction_date}"
+            ))]));
+        };
+        let relief_table = rate_tables.select_relief(jurisdiction, transaction_date);
 
-        if property_value > 1_500_000.0 {
-            tax += (property_value - 1_500_000.0) * 0.12;
-        }
-        if property_value > 925_000.0 {
-            let upper = property_value.min(1_500_000.0);
-            tax += (upper - 925_000.0) * 0.10;
-        }
-        if property_value > 250_000.0 {
-            let upper = property_value.min(925_000.0);
-            tax += (upper - 250_000.0) * 0.05;
-        }
-        if property_value > 125_000.0 {
-            let upper = property_value.min(250_000.0);
-            tax += (upper - 125_000.0) * 0.02;
+        let breakdown = modifiers::apply(
+            table,
+            relief_table,
+            property_value,
+            BuyerCategory {
+                first_time_buyer,
+                additional_property,
+                non_resident,
+            },
+        );
+
+        tracing::info!(
+            property_value = %property_value,
+            ?jurisdiction,
+            tax = %breakdown.total,
+            "calculated SDLT"
+        );
+        self.metrics.observe_calculation(
+            property_value.to_f64().unwrap_or_default(),
+            breakdown.total.to_f64().unwrap_or_default(),
+        );
+
+        let mut report = format!("SDLT for £{property_value:.2}:\n");
+        for line in &breakdown.lines {
+            report.push_str(&format!("  {}: £{:.2}\n", line.label, line.amount));
         }
-        // No tax for the first £125,000
+        report.push_str(&format!("  total: £{:.2}", breakdown.total));
+
+        Ok(CallToolResult::success(vec![Content::text(report)]))
+    }
+
+    #[tracing::instrument(skip(self), fields(total_budget = %total_budget, ?jurisdiction))]
+    #[tool(
+        description = "Work out the highest property price affordable within a total budget (price + SDLT)"
+    )]
+    async fn max_affordable_price(
+        &self,
+        #[tool(aggr)] AffordabilityRequest {
+            total_budget,
+            transaction_date,
+            jurisdiction,
+        }: AffordabilityRequest,
+    ) -> Result<CallToolResult, Error> {
+        self.metrics.record_call();
+
+        let rate_tables = self.rate_tables.read().await.clone();
+        let Some(table) = rate_tables.select(jurisdiction, transaction_date) else {
+            self.metrics.record_error();
+            return Ok(CallToolResult::success(vec![Content::text(format!(
+                "No rate table found for {jurisdiction:?} on {transaction_date}"
+            ))]));
+        };
+
+        let max_price = table.max_affordable_price(total_budget);
+        let tax = table.tax_for(max_price);
+
+        tracing::info!(max_price = %max_price, tax = %tax, "calculated affordability");
 
         Ok(CallToolResult::success(vec![Content::text(format!(
-            "SDLT for £{property_value:.2} is £{tax:.2}"
+            "With a budget of £{total_budget:.2} you can afford a property priced up to £{max_price:.2} (SDLT £{tax:.2})"
         ))]))
     }
+
+    #[tool(description = "Report tool-call metrics (Prometheus text format)")]
+    async fn metrics(&self) -> Result<CallToolResult, Error> {
+        Ok(CallToolResult::success(vec![Content::text(
+            self.metrics.render_prometheus(),
+        )]))
+    }
+
+    /// Re-reads the rate-table config (e.g. after a budget-day update) and
+    /// fires the broadcast wired up in `resources`. Under the stdio transport
+    /// the client then receives a `resources/updated` notification; the SSE
+    /// transport does not forward the reload to its clients.
+    #[tool(
+        description = "Reload the rate-table config from disk and notify the client (stdio transport only)"
+    )]
+    async fn reload_rates(&self) -> Result<CallToolResult, Error> {
+        self.do_reload_rates().await;
+        Ok(CallToolResult::success(vec![Content::text(
+            "Rate tables reloaded".to_string(),
+        )]))
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let service = Calculator::new()
+    tracing_subscriber::fmt::init();
+
+    let cli = Cli::parse();
+
+    match cli.transport {
+        Transport::Stdio => run_stdio().await,
+        Transport::Sse => run_sse(&cli.bind).await,
+    }
+}
+
+/// Serve the calculator over stdio - the original behaviour, for
+/// locally-spawned MCP clients.
+async fn run_stdio() -> Result<(), Box<dyn std::error::Error>> {
+    let calculator = Calculator::new();
+    let mut rate_tables_changed = calculator.subscribe_rate_tables();
+
+    let service = calculator
         .serve(stdio())
         .await
         .inspect_err(|e| eprintln!("{e}"))?;
+
+    // Forward rate-table reloads to subscribed clients as resources/updated notifications.
+    let peer = service.peer().clone();
+    tokio::spawn(async move {
+        while rate_tables_changed.recv().await.is_ok() {
+            let _ = peer
+                .notify_resource_updated(rmcp::model::ResourceUpdatedNotificationParam {
+                    uri: resources::RATE_TABLES_URI.to_string(),
+                })
+                .await;
+        }
+    });
+
     service.waiting().await?;
     Ok(())
 }
 
+/// Serve the calculator over rmcp's HTTP/SSE transport, for remote/browser
+/// MCP clients that can't spawn a local subprocess.
+// TODO: also mount a GET /metrics route on the SSE server's axum router
+// returning `Calculator::metrics`'s Prometheus text, once rmcp exposes the
+// router for customisation; the `metrics` tool covers this for now.
+async fn run_sse(bind: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let sse_server = rmcp::transport::sse_server::SseServer::serve(bind.parse()?).await?;
+
+    let calculator = Calculator::new();
+    let mut rate_tables_changed = calculator.subscribe_rate_tables();
+
+    let ct = sse_server.with_service(move || calculator.clone());
+
+    // TODO: fan this out per-connection once rmcp exposes per-peer access
+    // from the SSE server; for now just drain so the channel doesn't block.
+    tokio::spawn(async move { while rate_tables_changed.recv().await.is_ok() {} });
+
+    eprintln!("listening on {bind} (sse)");
+    ct.cancelled().await;
+    Ok(())
+}
+
 #[tool(tool_box)] // I forgot this line initially, you see no tools if you don't add it!  i.e response = tools:[]
 impl ServerHandler for Calculator {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some(
                 "This server provides a calculator tool
@@ -101,6 +311,36 @@ impl ServerHandler for Calculator {
             ),
         }
     }
-}
 
+    async fn list_resources(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<ListResourcesResult, Error> {
+        Ok(ListResourcesResult {
+            resources: vec![resources::rate_tables_resource()],
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<ReadResourceResult, Error> {
+        if uri != resources::RATE_TABLES_URI {
+            return Err(Error::resource_not_found(
+                "rate table resource not found",
+                Some(serde_json::json!({ "uri": uri })),
+            ));
+        }
 
+        let rate_tables = self.rate_tables.read().await.clone();
+        let json = serde_json::to_string_pretty(&*rate_tables)
+            .unwrap_or_else(|e| format!("failed to serialize rate tables: {e}"));
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(json, uri)],
+        })
+    }
+}
@@ -0,0 +1,124 @@
+//! Tool-call metrics: total call and error counters (shared by all tools,
+//! not broken down per tool) plus a running histogram of requested property
+//! values / computed tax, exposed as Prometheus text via the `metrics` tool
+//! under either transport. No separate HTTP metrics endpoint is served.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A minimal running histogram: bucket counts plus sum/count for the mean.
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: Mutex<Vec<(f64, u64)>>,
+    sum: Mutex<f64>,
+    count: AtomicU64,
+}
+
+const BUCKET_BOUNDS: &[f64] = &[
+    125_000.0,
+    250_000.0,
+    925_000.0,
+    1_500_000.0,
+    5_000_000.0,
+    f64::INFINITY,
+];
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: Mutex::new(BUCKET_BOUNDS.iter().map(|b| (*b, 0)).collect()),
+            sum: Mutex::new(0.0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value: f64) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        *self.sum.lock().unwrap() += value;
+        for (bound, count) in self.buckets.lock().unwrap().iter_mut() {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+    }
+
+    fn render(&self, name: &str, out: &mut String) {
+        for (bound, count) in self.buckets.lock().unwrap().iter() {
+            let le = if bound.is_infinite() {
+                "+Inf".to_string()
+            } else {
+                bound.to_string()
+            };
+            out.push_str(&format!("{name}_bucket{{le=\"{le}\"}} {count}\n"));
+        }
+        out.push_str(&format!("{name}_sum {}\n", *self.sum.lock().unwrap()));
+        out.push_str(&format!(
+            "{name}_count {}\n",
+            self.count.load(Ordering::Relaxed)
+        ));
+    }
+}
+
+/// Per-server instrumentation. Cloned as an `Arc` into `Calculator`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    calls_total: AtomicU64,
+    errors_total: AtomicU64,
+    property_value_histogram: Histogram,
+    tax_histogram: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            calls_total: AtomicU64::new(0),
+            errors_total: AtomicU64::new(0),
+            property_value_histogram: Histogram::new(),
+            tax_histogram: Histogram::new(),
+        }
+    }
+
+    pub fn record_call(&self) {
+        self.calls_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self) {
+        self.errors_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn observe_calculation(&self, property_value: f64, tax: f64) {
+        self.property_value_histogram.observe(property_value);
+        self.tax_histogram.observe(tax);
+    }
+
+    pub fn calls_total(&self) -> u64 {
+        self.calls_total.load(Ordering::Relaxed)
+    }
+
+    pub fn errors_total(&self) -> u64 {
+        self.errors_total.load(Ordering::Relaxed)
+    }
+
+    /// Renders all metrics in Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP mcp_sdlt_calls_total Total tool invocations\n");
+        out.push_str("# TYPE mcp_sdlt_calls_total counter\n");
+        out.push_str(&format!("mcp_sdlt_calls_total {}\n", self.calls_total()));
+
+        out.push_str("# HELP mcp_sdlt_errors_total Total tool invocations that errored\n");
+        out.push_str("# TYPE mcp_sdlt_errors_total counter\n");
+        out.push_str(&format!("mcp_sdlt_errors_total {}\n", self.errors_total()));
+
+        out.push_str("# HELP mcp_sdlt_property_value Requested property values\n");
+        out.push_str("# TYPE mcp_sdlt_property_value histogram\n");
+        self.property_value_histogram
+            .render("mcp_sdlt_property_value", &mut out);
+
+        out.push_str("# HELP mcp_sdlt_tax Computed SDLT\n");
+        out.push_str("# TYPE mcp_sdlt_tax histogram\n");
+        self.tax_histogram.render("mcp_sdlt_tax", &mut out);
+
+        out
+    }
+}
@@ -0,0 +1,223 @@
+//! Buyer-category modifiers layered on top of the base `RateTable` result:
+//! additional-property and non-resident surcharges, or first-time-buyer
+//! relief (mutually exclusive with the surcharges).
+
+use crate::rates::RateTable;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Flat surcharge added to every band's rate for additional-dwelling purchases.
+pub const SURCHARGE_ADDITIONAL_PROPERTY: Decimal = dec!(0.05);
+/// Flat surcharge added on top for non-resident buyers.
+pub const SURCHARGE_NON_RESIDENT: Decimal = dec!(0.02);
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuyerCategory {
+    pub first_time_buyer: bool,
+    pub additional_property: bool,
+    pub non_resident: bool,
+}
+
+/// One line of the breakdown: a label and the tax it contributed.
+#[derive(Debug, Clone)]
+pub struct BreakdownLine {
+    pub label: String,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Breakdown {
+    pub lines: Vec<BreakdownLine>,
+    pub total: Decimal,
+}
+
+/// Applies `category` to `property_value` against `table` (and `relief_table`
+/// when first-time-buyer relief applies), returning a full breakdown.
+pub fn apply(
+    table: &RateTable,
+    relief_table: Option<&RateTable>,
+    property_value: Decimal,
+    category: BuyerCategory,
+) -> Breakdown {
+    if category.first_time_buyer {
+        if let Some(relief) = relief_table {
+            let total = relief.tax_for(property_value);
+            return Breakdown {
+                lines: vec![BreakdownLine {
+                    label: "first-time-buyer relief".to_string(),
+                    amount: total,
+                }],
+                total,
+            };
+        }
+        // No FTB relief table configured for this jurisdiction - fall back to
+        // standard rates, but say so plainly rather than mislabeling the
+        // unmodified tax as relief.
+        let total = table.tax_for(property_value);
+        return Breakdown {
+            lines: vec![
+                BreakdownLine {
+                    label: "no first-time-buyer relief configured for this jurisdiction"
+                        .to_string(),
+                    amount: Decimal::ZERO,
+                },
+                BreakdownLine {
+                    label: "base tax".to_string(),
+                    amount: total,
+                },
+            ],
+            total,
+        };
+    }
+
+    let base_tax = table.tax_for(property_value);
+    let mut lines = vec![BreakdownLine {
+        label: "base tax".to_string(),
+        amount: base_tax,
+    }];
+
+    let mut surcharge = Decimal::ZERO;
+    if category.additional_property {
+        let amount =
+            table.tax_for_with_surcharge(property_value, SURCHARGE_ADDITIONAL_PROPERTY) - base_tax;
+        lines.push(BreakdownLine {
+            label: "additional-property surcharge".to_string(),
+            amount,
+        });
+        surcharge += SURCHARGE_ADDITIONAL_PROPERTY;
+    }
+    if category.non_resident {
+        let amount =
+            table.tax_for_with_surcharge(property_value, SURCHARGE_NON_RESIDENT) - base_tax;
+        lines.push(BreakdownLine {
+            label: "non-resident surcharge".to_string(),
+            amount,
+        });
+        surcharge += SURCHARGE_NON_RESIDENT;
+    }
+
+    let total = table.tax_for_with_surcharge(property_value, surcharge);
+
+    Breakdown { lines, total }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rates::RateTables;
+
+    #[test]
+    fn no_modifiers_is_just_base_tax() {
+        let table = RateTables::defaults().table.remove(0);
+        let breakdown = apply(&table, None, dec!(345_000), BuyerCategory::default());
+        assert_eq!(breakdown.total, dec!(7_250));
+        assert_eq!(breakdown.lines.len(), 1);
+        assert_eq!(breakdown.lines[0].label, "base tax");
+    }
+
+    #[test]
+    fn additional_property_surcharge_is_flat_on_every_band() {
+        let table = RateTables::defaults().table.remove(0);
+        let breakdown = apply(
+            &table,
+            None,
+            dec!(100_000),
+            BuyerCategory {
+                additional_property: true,
+                ..Default::default()
+            },
+        );
+        // Below the nil-rate threshold, the only tax is the 5% surcharge.
+        assert_eq!(breakdown.total, dec!(5_000));
+    }
+
+    #[test]
+    fn additional_property_and_non_resident_surcharges_stack() {
+        let table = RateTables::defaults().table.remove(0);
+        let breakdown = apply(
+            &table,
+            None,
+            dec!(100_000),
+            BuyerCategory {
+                additional_property: true,
+                non_resident: true,
+                ..Default::default()
+            },
+        );
+        // 5% + 2% flat surcharge on the whole value.
+        assert_eq!(breakdown.total, dec!(7_000));
+        assert_eq!(breakdown.lines.len(), 3);
+    }
+
+    #[test]
+    fn first_time_buyer_relief_uses_relief_table() {
+        let tables = RateTables::defaults();
+        let table = &tables.table[0];
+        let relief = &tables.relief_table[0];
+        let breakdown = apply(
+            table,
+            Some(relief),
+            dec!(350_000),
+            BuyerCategory {
+                first_time_buyer: true,
+                ..Default::default()
+            },
+        );
+        // 0% up to £300k, 5% on the remaining £50k.
+        assert_eq!(breakdown.total, dec!(2_500));
+        assert_eq!(breakdown.lines[0].label, "first-time-buyer relief");
+    }
+
+    #[test]
+    fn first_time_buyer_plus_surcharges_ignores_surcharges() {
+        // Mutually exclusive by design: FTB relief wins, surcharge flags are ignored.
+        let tables = RateTables::defaults();
+        let table = &tables.table[0];
+        let relief = &tables.relief_table[0];
+        let breakdown = apply(
+            table,
+            Some(relief),
+            dec!(350_000),
+            BuyerCategory {
+                first_time_buyer: true,
+                additional_property: true,
+                non_resident: true,
+            },
+        );
+        assert_eq!(breakdown.total, dec!(2_500));
+    }
+
+    #[test]
+    fn first_time_buyer_without_relief_table_falls_back_honestly() {
+        // Scotland/Wales have no relief_table entries configured - the
+        // breakdown must say so, not silently charge full tax under a
+        // "relief" label.
+        let tables = RateTables::defaults();
+        let table = &tables.table[0];
+        let breakdown = apply(
+            table,
+            None,
+            dec!(350_000),
+            BuyerCategory {
+                first_time_buyer: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(breakdown.total, table.tax_for(dec!(350_000)));
+        assert!(
+            breakdown
+                .lines
+                .iter()
+                .any(|l| l.label.contains("no first-time-buyer relief configured")),
+            "expected an explicit no-relief note, got: {:?}",
+            breakdown.lines
+        );
+        assert!(
+            !breakdown
+                .lines
+                .iter()
+                .any(|l| l.label == "first-time-buyer relief"),
+            "must not mislabel unmodified tax as relief"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! Date-aware, multi-jurisdiction stamp duty rate tables.
+//!
+//! Rates change over time (most recently the 2025 Autumn Budget) and differ
+//! between England/Northern Ireland (SDLT), Scotland (LBTT) and Wales (LTT).
+//! Rather than freezing one formula in `calculate_sdlt`, tables are loaded
+//! from a config file at startup and selected by `transaction_date` and
+//! `jurisdiction` on the request.
+
+use chrono::NaiveDate;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Jurisdiction {
+    #[default]
+    EnglandNi,
+    Scotland,
+    Wales,
+}
+
+/// One rate band: `rate` applies to the slice of property value above
+/// `lower_threshold` (and below the next band's threshold, if any).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Band {
+    pub lower_threshold: Decimal,
+    pub rate: Decimal,
+}
+
+/// A complete set of bands valid for a given jurisdiction over a date range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateTable {
+    pub jurisdiction: Jurisdiction,
+    pub effective_from: NaiveDate,
+    pub effective_to: Option<NaiveDate>,
+    /// Ordered ascending by `lower_threshold`.
+    pub bands: Vec<Band>,
+}
+
+impl RateTable {
+    fn covers(&self, date: NaiveDate) -> bool {
+        date >= self.effective_from && self.effective_to.is_none_or(|end| date < end)
+    }
+
+    /// Tax a property value by iterating bands bottom-up: for each band,
+    /// add `(min(value, next_threshold) - threshold) * rate` while the
+    /// value exceeds that band's threshold.
+    pub fn tax_for(&self, property_value: Decimal) -> Decimal {
+        self.tax_for_with_surcharge(property_value, Decimal::ZERO)
+    }
+
+    /// Same band walk as `tax_for`, but with `surcharge` added to every
+    /// band's rate - used for the additional-property/non-resident
+    /// surcharges, which apply uniformly across the whole table.
+    pub fn tax_for_with_surcharge(&self, property_value: Decimal, surcharge: Decimal) -> Decimal {
+        let mut tax = Decimal::ZERO;
+        for (i, band) in self.bands.iter().enumerate() {
+            if property_value <= band.lower_threshold {
+                continue;
+            }
+            let next_threshold = self
+                .bands
+                .get(i + 1)
+                .map(|b| b.lower_threshold)
+                .unwrap_or(Decimal::MAX);
+            let upper = property_value.min(next_threshold);
+            tax += (upper - band.lower_threshold) * (band.rate + surcharge);
+        }
+        tax.round_dp(2)
+    }
+
+    /// Inverse of `tax_for`: given a total budget (price + tax combined),
+    /// returns the highest property price whose price-plus-tax fits it.
+    ///
+    /// SDLT is piecewise-linear and strictly increasing, so this is solved
+    /// band-by-band from the bottom: within a band of rate `r`, an extra £1
+    /// of price costs £(1+r) of budget. Walk the bands accumulating both the
+    /// price covered and the budget consumed (`band_width * (1 + r)`); once
+    /// the remaining budget can't fully fund the next band, the answer is
+    /// that band's lower threshold plus `remaining_budget / (1 + r)`.
+    ///
+    /// The result is rounded *down* to the penny, never up or to-nearest:
+    /// rounding to-nearest can recommend a price whose actual tax (computed
+    /// on the rounded price) pushes the total a penny over budget, which is
+    /// never acceptable for an affordability figure.
+    pub fn max_affordable_price(&self, total_budget: Decimal) -> Decimal {
+        let mut remaining_budget = total_budget;
+        let mut price = Decimal::ZERO;
+
+        for (i, band) in self.bands.iter().enumerate() {
+            let next_threshold = self
+                .bands
+                .get(i + 1)
+                .map(|b| b.lower_threshold)
+                .unwrap_or(Decimal::MAX);
+            let cost_per_pound = Decimal::ONE + band.rate;
+
+            if next_threshold == Decimal::MAX {
+                return (band.lower_threshold + remaining_budget / cost_per_pound)
+                    .round_dp_with_strategy(2, RoundingStrategy::ToZero);
+            }
+
+            let band_width = next_threshold - band.lower_threshold;
+            let band_cost = band_width * cost_per_pound;
+
+            if remaining_budget < band_cost {
+                return (band.lower_threshold + remaining_budget / cost_per_pound)
+                    .round_dp_with_strategy(2, RoundingStrategy::ToZero);
+            }
+
+            remaining_budget -= band_cost;
+            price = next_threshold;
+        }
+
+        price.round_dp_with_strategy(2, RoundingStrategy::ToZero)
+    }
+}
+
+/// The full set of rate tables, as loaded from config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateTables {
+    pub table: Vec<RateTable>,
+    /// First-time-buyer relief bands, mutually exclusive with the
+    /// additional-property/non-resident surcharges - see `modifiers`.
+    #[serde(default)]
+    pub relief_table: Vec<RateTable>,
+}
+
+impl RateTables {
+    /// Loads rate tables from a TOML config file, falling back to the
+    /// built-in defaults (the long-standing England/NI bands) if the file
+    /// is absent, mirroring how CLI tools externalize defaults while still
+    /// working out of the box.
+    pub fn load(path: &str) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                toml::from_str(&contents).expect("rate table config must be valid TOML")
+            }
+            Err(_) => Self::defaults(),
+        }
+    }
+
+    pub fn defaults() -> Self {
+        use rust_decimal_macros::dec;
+
+        Self {
+            table: vec![RateTable {
+                jurisdiction: Jurisdiction::EnglandNi,
+                effective_from: NaiveDate::from_ymd_opt(2014, 12, 4).expect("valid date"),
+                effective_to: None,
+                bands: vec![
+                    Band {
+                        lower_threshold: dec!(0),
+                        rate: dec!(0),
+                    },
+                    Band {
+                        lower_threshold: dec!(125_000),
+                        rate: dec!(0.02),
+                    },
+                    Band {
+                        lower_threshold: dec!(250_000),
+                        rate: dec!(0.05),
+                    },
+                    Band {
+                        lower_threshold: dec!(925_000),
+                        rate: dec!(0.10),
+                    },
+                    Band {
+                        lower_threshold: dec!(1_500_000),
+                        rate: dec!(0.12),
+                    },
+                ],
+            }],
+            relief_table: vec![RateTable {
+                jurisdiction: Jurisdiction::EnglandNi,
+                effective_from: NaiveDate::from_ymd_opt(2014, 12, 4).expect("valid date"),
+                effective_to: None,
+                bands: vec![
+                    Band {
+                        lower_threshold: dec!(0),
+                        rate: dec!(0),
+                    },
+                    Band {
+                        lower_threshold: dec!(300_000),
+                        rate: dec!(0.05),
+                    },
+                ],
+            }],
+        }
+    }
+
+    /// Selects the table matching `jurisdiction` whose date range covers
+    /// `transaction_date`.
+    pub fn select(
+        &self,
+        jurisdiction: Jurisdiction,
+        transaction_date: NaiveDate,
+    ) -> Option<&RateTable> {
+        self.table
+            .iter()
+            .find(|t| t.jurisdiction == jurisdiction && t.covers(transaction_date))
+    }
+
+    /// Selects the first-time-buyer relief table for `jurisdiction`/`transaction_date`.
+    pub fn select_relief(
+        &self,
+        jurisdiction: Jurisdiction,
+        transaction_date: NaiveDate,
+    ) -> Option<&RateTable> {
+        self.relief_table
+            .iter()
+            .find(|t| t.jurisdiction == jurisdiction && t.covers(transaction_date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn england_ni() -> RateTable {
+        RateTables::defaults().table.remove(0)
+    }
+
+    #[test]
+    fn no_tax_below_first_threshold() {
+        let table = england_ni();
+        assert_eq!(table.tax_for(dec!(125_000)), dec!(0));
+        assert_eq!(table.tax_for(dec!(100_000)), dec!(0));
+    }
+
+    #[test]
+    fn hmrc_worked_example_345_000() {
+        // HMRC worked example: £345,000 property, standard rates.
+        // 0% on first £125k, 2% on next £125k (£2,500), 5% on remaining £95k (£4,750).
+        let table = england_ni();
+        assert_eq!(table.tax_for(dec!(345_000)), dec!(7_250));
+    }
+
+    #[test]
+    fn hmrc_worked_example_1_2_million() {
+        // 0% + 2%*125k (2,500) + 5%*675k (33,750) + 10%*275k (27,500) = 63,750
+        let table = england_ni();
+        assert_eq!(table.tax_for(dec!(1_200_000)), dec!(63_750));
+    }
+
+    #[test]
+    fn band_boundary_is_not_taxed_at_upper_rate() {
+        let table = england_ni();
+        // Exactly on a threshold pays nothing extra for that band (bands are
+        // "above" the threshold, not "at or above").
+        assert_eq!(table.tax_for(dec!(125_000)), dec!(0));
+        assert_eq!(table.tax_for(dec!(250_000)), dec!(2_500));
+        // One penny over a threshold taxes the excess at the new rate, but 5%
+        // of 1p is 0.05p, which rounds away to the penny.
+        let just_over = table.tax_for(dec!(250_000.01));
+        assert_eq!(just_over, dec!(2_500));
+    }
+
+    #[test]
+    fn surcharge_applies_to_every_band_including_nil_rate_band() {
+        let table = england_ni();
+        let with_surcharge = table.tax_for_with_surcharge(dec!(100_000), dec!(0.05));
+        // Below the nil-rate threshold the base rate is 0%, but the flat
+        // surcharge still applies to the whole value.
+        assert_eq!(with_surcharge, dec!(5_000));
+    }
+
+    #[test]
+    fn max_affordable_price_round_trips_with_tax_for() {
+        let table = england_ni();
+        for budget in [
+            dec!(100_000),
+            dec!(125_000),
+            dec!(125_002.29),
+            dec!(300_000),
+            dec!(999_999.99),
+            dec!(2_000_000),
+        ] {
+            let price = table.max_affordable_price(budget);
+            let total_cost = price + table.tax_for(price);
+            assert!(
+                total_cost <= budget,
+                "budget {budget} produced price {price} costing {total_cost} (over budget)"
+            );
+        }
+    }
+
+    #[test]
+    fn max_affordable_price_never_rounds_price_up() {
+        // Regression test: the price must be floored to the penny, not
+        // rounded to-nearest, or the recommended purchase can cost a penny
+        // more than the stated budget once tax is added back on.
+        let table = england_ni();
+        let budget = dec!(125_002.29);
+        let price = table.max_affordable_price(budget);
+        assert_eq!(price, dec!(125_002.24));
+        assert!(price + table.tax_for(price) <= budget);
+    }
+
+    #[test]
+    fn select_picks_table_by_jurisdiction_and_date() {
+        let tables = RateTables::defaults();
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        assert!(tables.select(Jurisdiction::EnglandNi, date).is_some());
+        assert!(tables.select(Jurisdiction::Scotland, date).is_none());
+    }
+
+    #[test]
+    fn select_relief_is_none_when_not_configured() {
+        let tables = RateTables::defaults();
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        assert!(tables
+            .select_relief(Jurisdiction::EnglandNi, date)
+            .is_some());
+        assert!(tables.select_relief(Jurisdiction::Scotland, date).is_none());
+    }
+}
@@ -0,0 +1,31 @@
+//! Exposes the active `RateTable` set as subscribable MCP resources, so
+//! long-running clients can stay current on rate changes without polling
+//! the `calculate_sdlt` tool.
+//!
+//! Reloads are broadcast over a `tokio::sync::broadcast` channel (the same
+//! pattern used for event/property-observation streams in async IPC
+//! clients): anyone holding a `RateTablesChangedRx` receiver learns a reload
+//! happened and can re-read the resource.
+
+use rmcp::model::{RawResource, Resource};
+use tokio::sync::broadcast;
+
+/// Fixed URI for the "current rate tables" resource.
+pub const RATE_TABLES_URI: &str = "rates://current";
+
+/// Sent on the broadcast channel whenever the rate tables are reloaded.
+#[derive(Debug, Clone)]
+pub struct RateTablesChanged;
+
+pub type RateTablesChangedTx = broadcast::Sender<RateTablesChanged>;
+pub type RateTablesChangedRx = broadcast::Receiver<RateTablesChanged>;
+
+/// Creates the broadcast channel used to notify subscribers of reloads.
+pub fn channel() -> (RateTablesChangedTx, RateTablesChangedRx) {
+    broadcast::channel(16)
+}
+
+/// The single resource this server exposes today: the whole `RateTables` set as JSON.
+pub fn rate_tables_resource() -> Resource {
+    Resource::new(RawResource::new(RATE_TABLES_URI, "SDLT rate tables"), None)
+}